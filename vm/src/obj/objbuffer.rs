@@ -0,0 +1,90 @@
+//! A small stand-in for CPython's `Py_buffer` / buffer protocol: types that can hand out a
+//! contiguous view of their bytes without copying implement [`BufferProtocol`] and are listed
+//! in [`find_buffer`], which [`get_buffer`] dispatches through.
+
+use std::cell::Ref;
+use std::ops::Deref;
+
+use crate::obj::objbytearray::PyByteArray;
+use crate::obj::objbytes::PyBytes;
+use crate::obj::objmemoryview::PyMemoryView;
+use crate::pyobject::{PyObjectRef, PyResult};
+use crate::vm::VirtualMachine;
+
+/// Shape/mutability metadata for a buffer export. RustPython only deals in one-dimensional
+/// byte buffers for now, so this is much smaller than CPython's `Py_buffer`.
+#[derive(Debug, Clone, Copy)]
+pub struct BufferOptions {
+    pub readonly: bool,
+    pub len: usize,
+}
+
+/// A borrowed view of a buffer's bytes.
+///
+/// `Slice` is used by buffers that can never be mutated out from under the view (`bytes`);
+/// `Guarded` is used by buffers backed by a `RefCell` (`bytearray`), so the standard
+/// `Ref` aliasing rules apply while the view is alive.
+pub enum BufferRef<'a> {
+    Slice(&'a [u8]),
+    Guarded(Ref<'a, [u8]>),
+}
+
+impl<'a> Deref for BufferRef<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            BufferRef::Slice(s) => s,
+            BufferRef::Guarded(r) => r,
+        }
+    }
+}
+
+/// Narrows a buffer view down to `[start, start + len)`, the way a `memoryview` slice has to
+/// without copying the underlying bytes. `start + len` must be within `buffer`'s bounds.
+pub fn slice_buffer_ref(buffer: BufferRef, start: usize, len: usize) -> BufferRef {
+    match buffer {
+        BufferRef::Slice(s) => BufferRef::Slice(&s[start..start + len]),
+        BufferRef::Guarded(r) => BufferRef::Guarded(Ref::map(r, |s| &s[start..start + len])),
+    }
+}
+
+/// Implemented by types that can expose a contiguous `&[u8]` view of themselves, the same role
+/// `Py_buffer` plays for `PyObject_GetBuffer` in CPython.
+pub trait BufferProtocol {
+    fn get_options(&self) -> BufferOptions;
+    fn get_bytes(&self) -> BufferRef;
+
+    /// Record that a buffer/memoryview export over `self` is now outstanding. Types that can
+    /// never be resized (`bytes`) don't need to track this.
+    fn inc_export(&self) {}
+    /// Undo a previous [`BufferProtocol::inc_export`].
+    fn dec_export(&self) {}
+}
+
+/// Looks up the buffer protocol for `obj` without raising, the way callers that merely want
+/// to *check* for buffer support (rather than require it) would use `PyObject_CheckBuffer`.
+pub fn find_buffer(obj: &PyObjectRef) -> Option<&dyn BufferProtocol> {
+    if let Some(bytes) = obj.payload::<PyBytes>() {
+        return Some(bytes as &dyn BufferProtocol);
+    }
+    if let Some(bytearray) = obj.payload::<PyByteArray>() {
+        return Some(bytearray as &dyn BufferProtocol);
+    }
+    if let Some(memoryview) = obj.payload::<PyMemoryView>() {
+        return Some(memoryview as &dyn BufferProtocol);
+    }
+    None
+}
+
+/// Looks up the buffer protocol for `obj`, the way `PyObject_GetBuffer` dispatches on a type's
+/// `tp_as_buffer` slot. New buffer-exporting types are added to [`find_buffer`] as they're
+/// implemented.
+pub fn get_buffer<'a>(vm: &VirtualMachine, obj: &'a PyObjectRef) -> PyResult<&'a dyn BufferProtocol> {
+    find_buffer(obj).ok_or_else(|| {
+        vm.new_type_error(format!(
+            "a bytes-like object is required, not '{}'",
+            obj.class().name
+        ))
+    })
+}
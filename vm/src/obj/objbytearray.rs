@@ -0,0 +1,184 @@
+use std::cell::{Cell, RefCell};
+
+use crate::function::OptionalArg;
+use crate::obj::objbuffer::{BufferOptions, BufferProtocol, BufferRef};
+use crate::obj::objbyteinner::PyByteInner;
+use crate::obj::objiter;
+use crate::obj::objtype::PyClassRef;
+use crate::pyobject::{PyClassImpl, PyContext, PyObjectRef, PyRef, PyResult, PyValue};
+use crate::vm::VirtualMachine;
+
+/// `bytearray`: the mutable sibling of `bytes`, sharing `PyByteInner` as its storage. Its
+/// storage sits behind a `RefCell` plus an export counter, so a live buffer export (typically
+/// a `memoryview`) blocks resizing instead of leaving a dangling slice.
+#[derive(Debug)]
+pub struct PyByteArray {
+    inner: RefCell<PyByteInner>,
+    exports: Cell<usize>,
+}
+
+type PyByteArrayRef = PyRef<PyByteArray>;
+
+impl PyByteArray {
+    pub fn new(elements: Vec<u8>) -> Self {
+        PyByteArray {
+            inner: RefCell::new(PyByteInner { elements }),
+            exports: Cell::new(0),
+        }
+    }
+
+    /// Mutating methods that change the object's length go through this first; any method
+    /// that only overwrites existing bytes (`__setitem__` on a single index) doesn't need to,
+    /// since it can't invalidate a slice that's already been handed out.
+    fn check_resizable(&self, vm: &VirtualMachine) -> PyResult<()> {
+        if self.exports.get() > 0 {
+            Err(vm.new_buffer_error(
+                "Existing exports of data: object cannot be re-sized".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl PyValue for PyByteArray {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.ctx.bytearray_type()
+    }
+}
+
+impl BufferProtocol for PyByteArray {
+    fn get_options(&self) -> BufferOptions {
+        BufferOptions {
+            readonly: false,
+            len: self.inner.borrow().len(),
+        }
+    }
+
+    fn get_bytes(&self) -> BufferRef {
+        BufferRef::Guarded(std::cell::Ref::map(self.inner.borrow(), |inner| {
+            inner.elements.as_slice()
+        }))
+    }
+
+    fn inc_export(&self) {
+        self.exports.set(self.exports.get() + 1);
+    }
+
+    fn dec_export(&self) {
+        debug_assert!(self.exports.get() > 0, "dec_export called with no outstanding export");
+        self.exports.set(self.exports.get().saturating_sub(1));
+    }
+}
+
+pub fn init(context: &PyContext) {
+    PyByteArrayRef::extend_class(context, &context.bytearray_type);
+    let bytearrayiterator_type = &context.bytearrayiterator_type;
+    extend_class!(context, bytearrayiterator_type, {
+            "__next__" => context.new_rustfunc(PyByteArrayIteratorRef::next),
+            "__iter__" => context.new_rustfunc(PyByteArrayIteratorRef::iter),
+    });
+}
+
+#[pyimpl(__inside_vm)]
+impl PyByteArrayRef {
+    #[pymethod(name = "__new__")]
+    fn bytearray_new(
+        cls: PyClassRef,
+        val_option: OptionalArg<PyObjectRef>,
+        enc_option: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyByteArrayRef> {
+        PyByteArray::new(PyByteInner::new(val_option, enc_option, vm)?.elements)
+            .into_ref_with_type(vm, cls)
+    }
+
+    #[pymethod(name = "__repr__")]
+    fn repr(self, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.new_str(format!("bytearray(b'{}')", self.inner.borrow().repr()?)))
+    }
+
+    #[pymethod(name = "__len__")]
+    fn len(self, _vm: &VirtualMachine) -> usize {
+        self.inner.borrow().len()
+    }
+
+    #[pymethod(name = "__getitem__")]
+    fn getitem(self, i: isize, vm: &VirtualMachine) -> PyResult<u8> {
+        let inner = self.inner.borrow();
+        let index = if i < 0 { i + inner.len() as isize } else { i };
+        inner
+            .elements
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| vm.new_index_error("bytearray index out of range".to_string()))
+    }
+
+    #[pymethod(name = "__setitem__")]
+    fn setitem(self, i: isize, value: u8, vm: &VirtualMachine) -> PyResult<()> {
+        // Overwriting an existing byte never changes the length, so an outstanding export is
+        // still safe to leave alone here.
+        let mut inner = self.inner.borrow_mut();
+        let index = if i < 0 { i + inner.len() as isize } else { i };
+        let slot = inner
+            .elements
+            .get_mut(index as usize)
+            .ok_or_else(|| vm.new_index_error("bytearray index out of range".to_string()))?;
+        *slot = value;
+        Ok(())
+    }
+
+    #[pymethod(name = "append")]
+    fn append(self, value: u8, vm: &VirtualMachine) -> PyResult<()> {
+        self.check_resizable(vm)?;
+        self.inner.borrow_mut().elements.push(value);
+        Ok(())
+    }
+
+    #[pymethod(name = "extend")]
+    fn extend(self, iterable: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+        self.check_resizable(vm)?;
+        let extra = vm.extract_elements::<u8>(&iterable)?;
+        self.inner.borrow_mut().elements.extend(extra);
+        Ok(())
+    }
+
+    #[pymethod(name = "__iter__")]
+    fn iter(self, _vm: &VirtualMachine) -> PyByteArrayIterator {
+        PyByteArrayIterator {
+            position: Cell::new(0),
+            bytearray: self,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PyByteArrayIterator {
+    position: Cell<usize>,
+    bytearray: PyByteArrayRef,
+}
+
+impl PyValue for PyByteArrayIterator {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.ctx.bytearrayiterator_type()
+    }
+}
+
+type PyByteArrayIteratorRef = PyRef<PyByteArrayIterator>;
+
+impl PyByteArrayIteratorRef {
+    fn next(self, vm: &VirtualMachine) -> PyResult<u8> {
+        let inner = self.bytearray.inner.borrow();
+        if self.position.get() < inner.len() {
+            let ret = inner.elements[self.position.get()];
+            self.position.set(self.position.get() + 1);
+            Ok(ret)
+        } else {
+            Err(objiter::new_stop_iteration(vm))
+        }
+    }
+
+    fn iter(self, _vm: &VirtualMachine) -> Self {
+        self
+    }
+}
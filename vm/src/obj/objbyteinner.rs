@@ -0,0 +1,637 @@
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+use std::ops::Range;
+
+use crate::function::OptionalArg;
+use crate::obj::objbuffer::get_buffer;
+use crate::obj::objcodecs::{self, ErrorHandling};
+use crate::obj::objint::PyInt;
+use crate::obj::objstr::PyString;
+use crate::obj::objtype;
+use crate::pyobject::{PyObjectRef, PyResult};
+use crate::vm::VirtualMachine;
+
+/// Either half of a `bytes`/`bytearray` `__getitem__`: a plain integer index, or a `slice`
+/// already resolved to `(start..stop, step)` against the sequence's current length.
+pub enum SequenceIndex {
+    Int(isize),
+    Slice(Range<isize>, isize),
+}
+
+pub fn sequence_index(vm: &VirtualMachine, needle: &PyObjectRef, len: usize) -> PyResult<SequenceIndex> {
+    if let Some(i) = needle.payload::<PyInt>() {
+        return Ok(SequenceIndex::Int(
+            i.as_bigint().to_owned().try_into().unwrap_or(isize::max_value()),
+        ));
+    }
+    if objtype::isinstance(needle, &vm.ctx.slice_type()) {
+        let to_isize = |vm: &VirtualMachine, attr: &str| -> PyResult<Option<isize>> {
+            let val = vm.get_attribute(needle.clone(), attr)?;
+            if vm.is_none(&val) {
+                Ok(None)
+            } else {
+                Ok(Some(vm.to_index(&val)?.to_owned().try_into().unwrap_or(0)))
+            }
+        };
+        let step = to_isize(vm, "step")?.unwrap_or(1);
+        if step == 0 {
+            return Err(vm.new_value_error("slice step cannot be zero".to_string()));
+        }
+        let default_start = if step < 0 { len as isize - 1 } else { 0 };
+        // Encoded so that `getslice`'s blanket "negative means count from the end" adjustment
+        // turns this back into plain `-1`, its real meaning ("run off before index 0").
+        let default_stop = if step < 0 { -(len as isize) - 1 } else { len as isize };
+        let start = to_isize(vm, "start")?.unwrap_or(default_start);
+        let stop = to_isize(vm, "stop")?.unwrap_or(default_stop);
+        return Ok(SequenceIndex::Slice(start..stop, step));
+    }
+    Err(vm.new_type_error("indices must be integers or slices".to_string()))
+}
+
+/// Clamps a raw (possibly negative or out-of-range) index to `0..=len`.
+pub fn clamp_index(index: isize, len: usize) -> usize {
+    if index < 0 {
+        (index + len as isize).max(0) as usize
+    } else {
+        (index as usize).min(len)
+    }
+}
+
+/// A reusable Boyer–Moore–Horspool matcher: the last-occurrence skip table for `needle` is
+/// built once, then `haystack` is scanned in jumps of up to `needle.len()` instead of the
+/// naive O(n·m) walk. `find`/`count`/`replace` share this scan; `rfind` checks each position
+/// from the right without using the skip table.
+pub struct ByteMatcher<'a> {
+    needle: &'a [u8],
+    skip: [usize; 256],
+}
+
+impl<'a> ByteMatcher<'a> {
+    pub fn new(needle: &'a [u8]) -> Self {
+        let mut skip = [needle.len().max(1); 256];
+        if needle.len() > 1 {
+            for (i, &b) in needle[..needle.len() - 1].iter().enumerate() {
+                skip[b as usize] = needle.len() - 1 - i;
+            }
+        }
+        ByteMatcher { needle, skip }
+    }
+
+    /// First occurrence of `needle` in `haystack` at or after `from`.
+    pub fn find(&self, haystack: &[u8], from: usize) -> Option<usize> {
+        if self.needle.is_empty() {
+            return Some(from.min(haystack.len()));
+        }
+        let n = self.needle.len();
+        let mut pos = from;
+        while pos + n <= haystack.len() {
+            if &haystack[pos..pos + n] == self.needle {
+                return Some(pos);
+            }
+            let last = haystack[pos + n - 1];
+            pos += self.skip[last as usize];
+        }
+        None
+    }
+
+    /// Last occurrence of `needle` in `haystack`.
+    pub fn rfind(&self, haystack: &[u8]) -> Option<usize> {
+        if self.needle.is_empty() {
+            return Some(haystack.len());
+        }
+        let n = self.needle.len();
+        (0..=haystack.len().saturating_sub(n))
+            .rev()
+            .find(|&pos| &haystack[pos..pos + n] == self.needle)
+    }
+
+    pub fn count(&self, haystack: &[u8]) -> usize {
+        let step = self.needle.len().max(1);
+        let mut count = 0;
+        let mut pos = 0;
+        while let Some(found) = self.find(haystack, pos) {
+            count += 1;
+            pos = found + step;
+            if pos > haystack.len() {
+                break;
+            }
+        }
+        count
+    }
+}
+
+/// The storage shared by `bytes` and `bytearray`. `bytes` wraps this directly; `bytearray`
+/// wraps it behind a borrow-tracked cell so that mutation can be rejected while a buffer
+/// export is outstanding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PyByteInner {
+    pub elements: Vec<u8>,
+}
+
+impl PyByteInner {
+    pub fn new(
+        val_option: OptionalArg<PyObjectRef>,
+        enc_option: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<Self> {
+        let val = match val_option {
+            OptionalArg::Present(val) => val,
+            OptionalArg::Missing => return Ok(PyByteInner::default()),
+        };
+
+        if let OptionalArg::Present(enc) = enc_option {
+            let string = val
+                .payload::<PyString>()
+                .ok_or_else(|| vm.new_type_error("encoding without a string argument".to_string()))?;
+            let encoding = enc
+                .payload::<PyString>()
+                .ok_or_else(|| vm.new_type_error("encoding must be str".to_string()))?;
+            return Ok(PyByteInner {
+                elements: objcodecs::encode(&encoding.value, &string.value, vm)?,
+            });
+        }
+
+        if let Some(i) = val.payload::<PyInt>() {
+            let len = i
+                .as_bigint()
+                .to_owned()
+                .try_into()
+                .map_err(|_| vm.new_value_error("negative count".to_string()))?;
+            return Ok(PyByteInner {
+                elements: vec![0; len],
+            });
+        }
+
+        // Any object implementing the buffer protocol is copied byte-for-byte, before we
+        // fall back to treating `val` as an iterable of ints.
+        if let Ok(buffer) = get_buffer(vm, &val) {
+            return Ok(PyByteInner {
+                elements: buffer.get_bytes().to_vec(),
+            });
+        }
+
+        let elements = vm.extract_elements::<u8>(&val)?;
+        Ok(PyByteInner { elements })
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn repr(&self) -> PyResult<String> {
+        let mut res = String::with_capacity(self.elements.len());
+        for b in &self.elements {
+            match b {
+                b'\\' | b'\'' => {
+                    res.push('\\');
+                    res.push(*b as char);
+                }
+                b'\n' => res.push_str("\\n"),
+                b'\r' => res.push_str("\\r"),
+                b'\t' => res.push_str("\\t"),
+                0x20..=0x7e => res.push(*b as char),
+                _ => res.push_str(&format!("\\x{:02x}", b)),
+            }
+        }
+        Ok(res)
+    }
+
+    pub fn hash(&self) -> usize {
+        let mut hasher = DefaultHasher::new();
+        self.elements.hash(&mut hasher);
+        hasher.finish() as usize
+    }
+
+    pub fn eq(&self, other: &PyByteInner, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.new_bool(self.elements == other.elements))
+    }
+
+    pub fn ge(&self, other: &PyByteInner, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.new_bool(self.elements >= other.elements))
+    }
+
+    pub fn le(&self, other: &PyByteInner, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.new_bool(self.elements <= other.elements))
+    }
+
+    pub fn gt(&self, other: &PyByteInner, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.new_bool(self.elements > other.elements))
+    }
+
+    pub fn lt(&self, other: &PyByteInner, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.new_bool(self.elements < other.elements))
+    }
+
+    /// Reads an argument that may be `bytes`, `bytearray`, or anything else exposing the
+    /// buffer protocol, the way CPython's `PyArg_ParseTuple("y*", ...)` accepts any of them.
+    pub fn coerce_bytes_like(vm: &VirtualMachine, obj: &PyObjectRef) -> PyResult<Vec<u8>> {
+        Ok(get_buffer(vm, obj)?.get_bytes().to_vec())
+    }
+
+    /// Like `coerce_bytes_like`, but also accepts a single int in `range(256)` as a one-byte
+    /// needle, the way `bytes.find`/`index`/`count`/`__contains__` do (`65 in b'ABC'`).
+    pub fn coerce_bytes_like_or_int(vm: &VirtualMachine, obj: &PyObjectRef) -> PyResult<Vec<u8>> {
+        if let Some(i) = obj.payload::<PyInt>() {
+            let n: i64 = i
+                .as_bigint()
+                .to_owned()
+                .try_into()
+                .map_err(|_| vm.new_value_error("byte must be in range(0, 256)".to_string()))?;
+            if !(0..256).contains(&n) {
+                return Err(vm.new_value_error("byte must be in range(0, 256)".to_string()));
+            }
+            return Ok(vec![n as u8]);
+        }
+        Self::coerce_bytes_like(vm, obj)
+    }
+
+    pub fn getitem(&self, needle: SequenceIndex, vm: &VirtualMachine) -> PyResult<PyByteInnerIndexResult> {
+        match needle {
+            SequenceIndex::Int(i) => {
+                let index = if i < 0 { i + self.len() as isize } else { i };
+                self.elements
+                    .get(index as usize)
+                    .copied()
+                    .map(PyByteInnerIndexResult::Byte)
+                    .ok_or_else(|| vm.new_index_error("index out of range".to_string()))
+            }
+            SequenceIndex::Slice(range, step) => Ok(PyByteInnerIndexResult::Bytes(self.getslice(range, step))),
+        }
+    }
+
+    pub fn getslice(&self, range: Range<isize>, step: isize) -> Vec<u8> {
+        let len = self.len();
+        if step == 1 {
+            let start = clamp_index(range.start, len);
+            let stop = clamp_index(range.end, len).max(start);
+            return self.elements[start..stop].to_vec();
+        }
+        // `sequence_index` only resolves attribute lookups, not index semantics, so negative
+        // `start`/`stop` still need the usual "count from the end" adjustment here — the same
+        // one the `step == 1` fast path gets for free from `clamp_index`.
+        let normalize = |i: isize| if i < 0 { i + len as isize } else { i };
+        let start = normalize(range.start);
+        let stop = normalize(range.end);
+        let mut result = Vec::new();
+        let mut i = start;
+        if step > 0 {
+            while i < stop {
+                if i >= 0 && (i as usize) < len {
+                    result.push(self.elements[i as usize]);
+                }
+                i += step;
+            }
+        } else {
+            while i > stop {
+                if i >= 0 && (i as usize) < len {
+                    result.push(self.elements[i as usize]);
+                }
+                i += step;
+            }
+        }
+        result
+    }
+
+    pub fn add(&self, other: &PyByteInner) -> PyByteInner {
+        let mut elements = self.elements.clone();
+        elements.extend_from_slice(&other.elements);
+        PyByteInner { elements }
+    }
+
+    pub fn mul(&self, n: isize) -> PyByteInner {
+        let n = n.max(0) as usize;
+        PyByteInner {
+            elements: self.elements.repeat(n),
+        }
+    }
+
+    pub fn contains(&self, needle: &[u8]) -> bool {
+        ByteMatcher::new(needle).find(&self.elements, 0).is_some()
+    }
+
+    /// `start`/`stop` bound the search the way `find`/`rfind`/`index`/`count`'s optional
+    /// arguments do in CPython; callers resolve negative/missing bounds via `clamp_index`
+    /// before getting here.
+    pub fn find(&self, needle: &[u8], start: usize, stop: usize) -> Option<usize> {
+        let len = self.elements.len();
+        let start = start.min(len);
+        let stop = stop.min(len).max(start);
+        ByteMatcher::new(needle)
+            .find(&self.elements[start..stop], 0)
+            .map(|i| i + start)
+    }
+
+    pub fn rfind(&self, needle: &[u8], start: usize, stop: usize) -> Option<usize> {
+        let len = self.elements.len();
+        let start = start.min(len);
+        let stop = stop.min(len).max(start);
+        ByteMatcher::new(needle)
+            .rfind(&self.elements[start..stop])
+            .map(|i| i + start)
+    }
+
+    pub fn count(&self, needle: &[u8], start: usize, stop: usize) -> usize {
+        let len = self.elements.len();
+        let start = start.min(len);
+        let stop = stop.min(len).max(start);
+        ByteMatcher::new(needle).count(&self.elements[start..stop])
+    }
+
+    pub fn startswith(&self, prefix: &[u8]) -> bool {
+        self.elements.starts_with(prefix)
+    }
+
+    pub fn endswith(&self, suffix: &[u8]) -> bool {
+        self.elements.ends_with(suffix)
+    }
+
+    /// With `sep` (never empty — callers reject that with a `ValueError` before reaching
+    /// here), splits on exact matches of `sep`. Without one, splits on runs of ASCII
+    /// whitespace and drops empty pieces, like `str.split()`.
+    pub fn split(&self, sep: Option<&[u8]>, maxsplit: Option<usize>) -> Vec<Vec<u8>> {
+        match sep {
+            Some(sep) => {
+                let matcher = ByteMatcher::new(sep);
+                let mut parts = Vec::new();
+                let mut start = 0;
+                while maxsplit.map_or(true, |max| parts.len() < max) {
+                    match matcher.find(&self.elements, start) {
+                        Some(pos) => {
+                            parts.push(self.elements[start..pos].to_vec());
+                            start = pos + sep.len();
+                        }
+                        None => break,
+                    }
+                }
+                parts.push(self.elements[start..].to_vec());
+                parts
+            }
+            None => {
+                let data = &self.elements;
+                let mut parts = Vec::new();
+                let mut start = 0;
+                while let Some(offset) = data[start..].iter().position(|b| !b.is_ascii_whitespace()) {
+                    start += offset;
+                    let end = match maxsplit {
+                        Some(max) if parts.len() >= max => data.len(),
+                        _ => data[start..].iter().position(|b| b.is_ascii_whitespace()).map_or(data.len(), |i| start + i),
+                    };
+                    parts.push(data[start..end].to_vec());
+                    start = end;
+                }
+                parts
+            }
+        }
+    }
+
+    /// Unlike `split`, this has to scan from the right: once `maxsplit` is reached, whatever's
+    /// left becomes the *first* element verbatim, whitespace and all, rather than being
+    /// rejoined from already-split (and therefore already-whitespace-collapsed) pieces.
+    pub fn rsplit(&self, sep: Option<&[u8]>, maxsplit: Option<usize>) -> Vec<Vec<u8>> {
+        if maxsplit.is_none() {
+            return self.split(sep, None);
+        }
+        let max = maxsplit.unwrap();
+        let data = &self.elements;
+        let mut parts = Vec::new();
+        match sep {
+            Some(sep) => {
+                let matcher = ByteMatcher::new(sep);
+                let mut end = data.len();
+                while parts.len() < max {
+                    match matcher.rfind(&data[..end]) {
+                        Some(pos) => {
+                            parts.push(data[pos + sep.len()..end].to_vec());
+                            end = pos;
+                        }
+                        None => break,
+                    }
+                }
+                parts.push(data[..end].to_vec());
+            }
+            None => {
+                let mut end = data.len();
+                while end > 0 && data[end - 1].is_ascii_whitespace() {
+                    end -= 1;
+                }
+                while parts.len() < max && end > 0 {
+                    let word_end = end;
+                    while end > 0 && !data[end - 1].is_ascii_whitespace() {
+                        end -= 1;
+                    }
+                    parts.push(data[end..word_end].to_vec());
+                    while end > 0 && data[end - 1].is_ascii_whitespace() {
+                        end -= 1;
+                    }
+                }
+                if end > 0 {
+                    parts.push(data[..end].to_vec());
+                }
+            }
+        }
+        parts.reverse();
+        parts
+    }
+
+    /// Splits on `\n`, `\r`, and `\r\n` without discarding empty lines, the way
+    /// `bytes.splitlines()` does (`b'a\n\nb'` keeps its blank middle line).
+    pub fn splitlines(&self) -> Vec<Vec<u8>> {
+        let data = &self.elements;
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut i = 0;
+        while i < data.len() {
+            match data[i] {
+                b'\r' => {
+                    lines.push(data[start..i].to_vec());
+                    i += if data.get(i + 1) == Some(&b'\n') { 2 } else { 1 };
+                    start = i;
+                }
+                b'\n' => {
+                    lines.push(data[start..i].to_vec());
+                    i += 1;
+                    start = i;
+                }
+                _ => i += 1,
+            }
+        }
+        if start < data.len() {
+            lines.push(data[start..].to_vec());
+        }
+        lines
+    }
+
+    pub fn strip(&self, chars: Option<&[u8]>) -> Vec<u8> {
+        let is_strippable = |b: &u8| match chars {
+            Some(chars) => chars.contains(b),
+            None => b.is_ascii_whitespace(),
+        };
+        let start = self.elements.iter().position(|b| !is_strippable(b)).unwrap_or(self.elements.len());
+        let stop = self
+            .elements
+            .iter()
+            .rposition(|b| !is_strippable(b))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if start >= stop {
+            Vec::new()
+        } else {
+            self.elements[start..stop].to_vec()
+        }
+    }
+
+    pub fn join(&self, parts: &[Vec<u8>]) -> Vec<u8> {
+        let mut result = Vec::new();
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                result.extend_from_slice(&self.elements);
+            }
+            result.extend_from_slice(part);
+        }
+        result
+    }
+
+    pub fn replace(&self, old: &[u8], new: &[u8], count: Option<usize>) -> Vec<u8> {
+        if old.is_empty() {
+            // An empty needle "matches" at every position, including past the last byte, so
+            // `new` is inserted before each byte and once more at the very end.
+            let limit = count.unwrap_or(self.elements.len() + 1);
+            let mut result = Vec::new();
+            for (i, &b) in self.elements.iter().enumerate() {
+                if i < limit {
+                    result.extend_from_slice(new);
+                }
+                result.push(b);
+            }
+            if self.elements.len() < limit {
+                result.extend_from_slice(new);
+            }
+            return result;
+        }
+        let matcher = ByteMatcher::new(old);
+        let mut result = Vec::new();
+        let mut start = 0;
+        let mut replaced = 0;
+        while count.map_or(true, |max| replaced < max) {
+            match matcher.find(&self.elements, start) {
+                Some(pos) => {
+                    result.extend_from_slice(&self.elements[start..pos]);
+                    result.extend_from_slice(new);
+                    start = pos + old.len();
+                    replaced += 1;
+                }
+                None => break,
+            }
+        }
+        result.extend_from_slice(&self.elements[start..]);
+        result
+    }
+
+    pub fn decode(&self, encoding: &str, errors: &str, vm: &VirtualMachine) -> PyResult<String> {
+        let errors = ErrorHandling::parse(errors, vm)?;
+        objcodecs::decode(encoding, &self.elements, errors, vm)
+    }
+
+    /// Lowercase hex, with `sep` (if given) inserted between consecutive bytes (`ab-cd`).
+    pub fn hex(&self, sep: Option<u8>) -> String {
+        let mut s = String::with_capacity(self.elements.len() * 2);
+        for (i, b) in self.elements.iter().enumerate() {
+            if i > 0 {
+                if let Some(sep) = sep {
+                    s.push(sep as char);
+                }
+            }
+            s.push_str(&format!("{:02x}", b));
+        }
+        s
+    }
+
+    /// Parses whitespace-tolerant hex, the inverse of [`PyByteInner::hex`].
+    pub fn fromhex(s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+        let digits: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if digits.len() % 2 != 0 {
+            return Err(vm.new_value_error("non-hexadecimal number found in fromhex() arg".to_string()));
+        }
+        digits
+            .chunks(2)
+            .map(|pair| {
+                let byte: String = pair.iter().collect();
+                u8::from_str_radix(&byte, 16).map_err(|_| {
+                    vm.new_value_error("non-hexadecimal number found in fromhex() arg".to_string())
+                })
+            })
+            .collect()
+    }
+}
+
+/// What `__getitem__` returns: a single byte for an int index, or a fresh `bytes` payload for
+/// a slice.
+pub enum PyByteInnerIndexResult {
+    Byte(u8),
+    Bytes(Vec<u8>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inner(s: &[u8]) -> PyByteInner {
+        PyByteInner {
+            elements: s.to_vec(),
+        }
+    }
+
+    #[test]
+    fn getslice_reversed_with_explicit_negative_bounds() {
+        assert_eq!(inner(b"hello").getslice(-1..-6, -1), b"olleh");
+        assert_eq!(inner(b"hello").getslice(-2..0, -1), b"lle");
+    }
+
+    #[test]
+    fn getslice_full_reverse_via_defaults() {
+        assert_eq!(inner(b"hello").getslice(4..-6, -1), b"olleh");
+    }
+
+    #[test]
+    fn splitlines_keeps_blank_lines_and_merges_crlf() {
+        assert_eq!(
+            inner(b"a\n\nb").splitlines(),
+            vec![b"a".to_vec(), b"".to_vec(), b"b".to_vec()]
+        );
+        assert_eq!(inner(b"\n").splitlines(), vec![b"".to_vec()]);
+        assert_eq!(inner(b"").splitlines(), Vec::<Vec<u8>>::new());
+        assert_eq!(
+            inner(b"a\r\nb").splitlines(),
+            vec![b"a".to_vec(), b"b".to_vec()]
+        );
+    }
+
+    #[test]
+    fn rsplit_preserves_original_whitespace_in_the_remainder() {
+        assert_eq!(
+            inner(b"a\tb c").rsplit(None, Some(1)),
+            vec![b"a\tb".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(
+            inner(b"a,b,c").rsplit(Some(b","), Some(1)),
+            vec![b"a,b".to_vec(), b"c".to_vec()]
+        );
+    }
+
+    #[test]
+    fn replace_with_empty_needle_inserts_between_every_byte() {
+        assert_eq!(inner(b"abc").replace(b"", b"-", None), b"-a-b-c-");
+        assert_eq!(inner(b"abc").replace(b"", b"-", Some(2)), b"-a-bc");
+        assert_eq!(inner(b"").replace(b"", b"-", None), b"-");
+    }
+
+    #[test]
+    fn find_rfind_count_respect_start_and_stop() {
+        let data = inner(b"abcabc");
+        assert_eq!(data.find(b"a", 1, 6), Some(3));
+        assert_eq!(data.find(b"a", 4, 6), None);
+        assert_eq!(data.rfind(b"a", 0, 4), Some(3));
+        assert_eq!(data.rfind(b"a", 0, 1), Some(0));
+        assert_eq!(data.count(b"a", 1, 6), 1);
+    }
+}
@@ -5,7 +5,8 @@ use std::ops::Deref;
 use crate::function::OptionalArg;
 use crate::pyobject::{PyClassImpl, PyContext, PyObjectRef, PyRef, PyResult, PyValue};
 
-use super::objbyteinner::PyByteInner;
+use super::objbuffer::{BufferOptions, BufferProtocol, BufferRef};
+use super::objbyteinner::{clamp_index, sequence_index, PyByteInner, PyByteInnerIndexResult};
 use super::objiter;
 use super::objtype::PyClassRef;
 
@@ -47,6 +48,19 @@ impl PyValue for PyBytes {
     }
 }
 
+impl BufferProtocol for PyBytes {
+    fn get_options(&self) -> BufferOptions {
+        BufferOptions {
+            readonly: true,
+            len: self.inner.len(),
+        }
+    }
+
+    fn get_bytes(&self) -> BufferRef {
+        BufferRef::Slice(&self.inner.elements)
+    }
+}
+
 pub fn get_value<'a>(obj: &'a PyObjectRef) -> impl Deref<Target = Vec<u8>> + 'a {
     &obj.payload::<PyBytes>().unwrap().inner.elements
 }
@@ -58,6 +72,8 @@ pub fn init(context: &PyContext) {
             "__next__" => context.new_rustfunc(PyBytesIteratorRef::next),
             "__iter__" => context.new_rustfunc(PyBytesIteratorRef::iter),
     });
+    super::objmemoryview::init(context);
+    super::objbytearray::init(context);
 }
 
 #[pyimpl(__inside_vm)]
@@ -128,6 +144,262 @@ impl PyBytesRef {
             bytes: self,
         }
     }
+
+    #[pymethod(name = "__getitem__")]
+    fn getitem(self, needle: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        match self.inner.getitem(sequence_index(vm, &needle, self.inner.len())?, vm)? {
+            PyByteInnerIndexResult::Byte(b) => Ok(vm.new_int(b)),
+            PyByteInnerIndexResult::Bytes(elements) => Ok(vm.new_bytes(elements)),
+        }
+    }
+
+    #[pymethod(name = "__add__")]
+    fn add(self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        match_class!(other,
+        bytes @ PyBytes => Ok(vm.new_bytes(self.inner.add(&bytes.inner).elements)),
+        _  => Ok(vm.ctx.not_implemented()))
+    }
+
+    #[pymethod(name = "__mul__")]
+    fn mul(self, n: isize, vm: &VirtualMachine) -> PyObjectRef {
+        vm.new_bytes(self.inner.mul(n).elements)
+    }
+
+    #[pymethod(name = "__contains__")]
+    fn contains(self, needle: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+        Ok(self.inner.contains(&PyByteInner::coerce_bytes_like_or_int(vm, &needle)?))
+    }
+
+    #[pymethod(name = "find")]
+    fn find(
+        self,
+        sub: PyObjectRef,
+        start: OptionalArg<PyObjectRef>,
+        end: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<isize> {
+        let needle = PyByteInner::coerce_bytes_like_or_int(vm, &sub)?;
+        let (start, end) = search_bounds(start, end, self.inner.len(), vm)?;
+        Ok(self
+            .inner
+            .find(&needle, start, end)
+            .map(|i| i as isize)
+            .unwrap_or(-1))
+    }
+
+    #[pymethod(name = "rfind")]
+    fn rfind(
+        self,
+        sub: PyObjectRef,
+        start: OptionalArg<PyObjectRef>,
+        end: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<isize> {
+        let needle = PyByteInner::coerce_bytes_like_or_int(vm, &sub)?;
+        let (start, end) = search_bounds(start, end, self.inner.len(), vm)?;
+        Ok(self
+            .inner
+            .rfind(&needle, start, end)
+            .map(|i| i as isize)
+            .unwrap_or(-1))
+    }
+
+    #[pymethod(name = "index")]
+    fn index(
+        self,
+        sub: PyObjectRef,
+        start: OptionalArg<PyObjectRef>,
+        end: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<usize> {
+        let needle = PyByteInner::coerce_bytes_like_or_int(vm, &sub)?;
+        let (start, end) = search_bounds(start, end, self.inner.len(), vm)?;
+        self.inner
+            .find(&needle, start, end)
+            .ok_or_else(|| vm.new_value_error("subsection not found".to_string()))
+    }
+
+    #[pymethod(name = "count")]
+    fn count(
+        self,
+        sub: PyObjectRef,
+        start: OptionalArg<PyObjectRef>,
+        end: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<usize> {
+        let needle = PyByteInner::coerce_bytes_like_or_int(vm, &sub)?;
+        let (start, end) = search_bounds(start, end, self.inner.len(), vm)?;
+        Ok(self.inner.count(&needle, start, end))
+    }
+
+    #[pymethod(name = "startswith")]
+    fn startswith(self, prefix: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+        let prefix = PyByteInner::coerce_bytes_like(vm, &prefix)?;
+        Ok(self.inner.startswith(&prefix))
+    }
+
+    #[pymethod(name = "endswith")]
+    fn endswith(self, suffix: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+        let suffix = PyByteInner::coerce_bytes_like(vm, &suffix)?;
+        Ok(self.inner.endswith(&suffix))
+    }
+
+    #[pymethod(name = "split")]
+    fn split(
+        self,
+        sep: OptionalArg<PyObjectRef>,
+        maxsplit: OptionalArg<isize>,
+        vm: &VirtualMachine,
+    ) -> PyResult<Vec<PyObjectRef>> {
+        let sep = sep.into_option().map(|s| PyByteInner::coerce_bytes_like(vm, &s)).transpose()?;
+        reject_empty_separator(sep.as_deref(), vm)?;
+        let maxsplit = non_negative(maxsplit);
+        Ok(self
+            .inner
+            .split(sep.as_deref(), maxsplit)
+            .into_iter()
+            .map(|part| vm.new_bytes(part))
+            .collect())
+    }
+
+    #[pymethod(name = "rsplit")]
+    fn rsplit(
+        self,
+        sep: OptionalArg<PyObjectRef>,
+        maxsplit: OptionalArg<isize>,
+        vm: &VirtualMachine,
+    ) -> PyResult<Vec<PyObjectRef>> {
+        let sep = sep.into_option().map(|s| PyByteInner::coerce_bytes_like(vm, &s)).transpose()?;
+        reject_empty_separator(sep.as_deref(), vm)?;
+        let maxsplit = non_negative(maxsplit);
+        Ok(self
+            .inner
+            .rsplit(sep.as_deref(), maxsplit)
+            .into_iter()
+            .map(|part| vm.new_bytes(part))
+            .collect())
+    }
+
+    #[pymethod(name = "splitlines")]
+    fn splitlines(self, vm: &VirtualMachine) -> Vec<PyObjectRef> {
+        self.inner
+            .splitlines()
+            .into_iter()
+            .map(|line| vm.new_bytes(line))
+            .collect()
+    }
+
+    #[pymethod(name = "strip")]
+    fn strip(self, chars: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult {
+        let chars = chars.into_option().map(|c| PyByteInner::coerce_bytes_like(vm, &c)).transpose()?;
+        Ok(vm.new_bytes(self.inner.strip(chars.as_deref())))
+    }
+
+    #[pymethod(name = "join")]
+    fn join(self, iterable: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let parts = vm
+            .extract_elements::<PyObjectRef>(&iterable)?
+            .iter()
+            .map(|part| PyByteInner::coerce_bytes_like(vm, part))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(vm.new_bytes(self.inner.join(&parts)))
+    }
+
+    #[pymethod(name = "replace")]
+    fn replace(
+        self,
+        old: PyObjectRef,
+        new: PyObjectRef,
+        count: OptionalArg<isize>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        let old = PyByteInner::coerce_bytes_like(vm, &old)?;
+        let new = PyByteInner::coerce_bytes_like(vm, &new)?;
+        Ok(vm.new_bytes(self.inner.replace(&old, &new, non_negative(count))))
+    }
+
+    #[pymethod(name = "decode")]
+    fn decode(
+        self,
+        encoding: OptionalArg<PyObjectRef>,
+        errors: OptionalArg<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<String> {
+        let encoding = match encoding.into_option() {
+            Some(enc) => vm.to_str(&enc)?.as_str().to_string(),
+            None => "utf-8".to_string(),
+        };
+        let errors = match errors.into_option() {
+            Some(err) => vm.to_str(&err)?.as_str().to_string(),
+            None => "strict".to_string(),
+        };
+        self.inner.decode(&encoding, &errors, vm)
+    }
+
+    #[pymethod(name = "hex")]
+    fn hex(self, sep: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult<String> {
+        let sep = match sep.into_option() {
+            Some(sep) => {
+                let sep = vm.to_str(&sep)?;
+                let bytes = sep.as_str().as_bytes();
+                if bytes.len() != 1 {
+                    return Err(vm.new_value_error("sep must be a single character".to_string()));
+                }
+                Some(bytes[0])
+            }
+            None => None,
+        };
+        Ok(self.inner.hex(sep))
+    }
+
+    #[pyclassmethod(name = "fromhex")]
+    fn fromhex(cls: PyClassRef, string: PyObjectRef, vm: &VirtualMachine) -> PyResult<PyBytesRef> {
+        let s = vm.to_str(&string)?;
+        PyBytes {
+            inner: PyByteInner {
+                elements: PyByteInner::fromhex(s.as_str(), vm)?,
+            },
+        }
+        .into_ref_with_type(vm, cls)
+    }
+}
+
+/// `maxsplit`/`count` arguments are `-1` (no limit) by convention in CPython; translate that
+/// sentinel (or a missing argument) into `None`.
+fn non_negative(arg: OptionalArg<isize>) -> Option<usize> {
+    match arg {
+        OptionalArg::Present(n) if n >= 0 => Some(n as usize),
+        _ => None,
+    }
+}
+
+/// `split`/`rsplit` with an explicit empty `sep` can't produce a sensible result (there's no
+/// gap to split on), so CPython rejects it outright rather than looping forever.
+fn reject_empty_separator(sep: Option<&[u8]>, vm: &VirtualMachine) -> PyResult<()> {
+    match sep {
+        Some(sep) if sep.is_empty() => Err(vm.new_value_error("empty separator".to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Resolves `find`/`rfind`/`index`/`count`'s optional `start`/`end` slice-style bounds
+/// (negative counts from the end, missing means "the whole thing") to a plain index range.
+fn search_bounds(
+    start: OptionalArg<PyObjectRef>,
+    end: OptionalArg<PyObjectRef>,
+    len: usize,
+    vm: &VirtualMachine,
+) -> PyResult<(usize, usize)> {
+    let to_isize = |arg: OptionalArg<PyObjectRef>, default: isize| -> PyResult<isize> {
+        match arg.into_option() {
+            None => Ok(default),
+            Some(v) if vm.is_none(&v) => Ok(default),
+            Some(v) => Ok(vm.to_index(&v)?.to_owned().try_into().unwrap_or(0)),
+        }
+    };
+    let start = clamp_index(to_isize(start, 0)?, len);
+    let end = clamp_index(to_isize(end, len as isize)?, len).max(start);
+    Ok((start, end))
 }
 
 #[derive(Debug)]
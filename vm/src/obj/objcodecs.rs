@@ -0,0 +1,374 @@
+//! A small codec registry: `bytes.decode`/`str.encode` and friends look an encoding name up
+//! here instead of hardcoding one.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+
+use crate::pyobject::PyResult;
+use crate::vm::VirtualMachine;
+
+/// How a decoder should react to bytes that aren't valid in the target encoding, mirroring
+/// CPython's `errors` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorHandling {
+    Strict,
+    Ignore,
+    Replace,
+}
+
+impl ErrorHandling {
+    pub fn parse(name: &str, vm: &VirtualMachine) -> PyResult<Self> {
+        match name {
+            "strict" => Ok(ErrorHandling::Strict),
+            "ignore" => Ok(ErrorHandling::Ignore),
+            "replace" => Ok(ErrorHandling::Replace),
+            other => Err(vm.new_value_error(format!("unknown error handler name '{}'", other))),
+        }
+    }
+}
+
+pub type Encoder = fn(&str, &VirtualMachine) -> PyResult<Vec<u8>>;
+pub type Decoder = fn(&[u8], ErrorHandling, &VirtualMachine) -> PyResult<String>;
+
+#[derive(Clone, Copy)]
+pub struct Codec {
+    pub encode: Encoder,
+    pub decode: Decoder,
+}
+
+lazy_static! {
+    static ref CODECS: RwLock<HashMap<String, Codec>> = RwLock::new(builtin_codecs());
+}
+
+fn builtin_codecs() -> HashMap<String, Codec> {
+    let mut map = HashMap::new();
+    map.insert(
+        "utf-8".to_string(),
+        Codec {
+            encode: encode_utf8,
+            decode: decode_utf8,
+        },
+    );
+    map.insert(
+        "ascii".to_string(),
+        Codec {
+            encode: encode_ascii,
+            decode: decode_ascii,
+        },
+    );
+    map.insert(
+        "latin-1".to_string(),
+        Codec {
+            encode: encode_latin1,
+            decode: decode_latin1,
+        },
+    );
+    map.insert(
+        "utf-16".to_string(),
+        Codec {
+            encode: encode_utf16,
+            decode: decode_utf16,
+        },
+    );
+    map.insert(
+        "base64".to_string(),
+        Codec {
+            encode: encode_base64,
+            decode: decode_base64,
+        },
+    );
+    map.insert(
+        "hex".to_string(),
+        Codec {
+            encode: encode_hex,
+            decode: decode_hex,
+        },
+    );
+    map
+}
+
+/// `utf_8`, `UTF8`, and `utf-8` all need to land on the same registry key; CPython's own
+/// encoding lookup is similarly forgiving about case, underscores, and missing separators.
+fn normalize(name: &str) -> String {
+    let name = name.to_lowercase().replace('_', "-");
+    match name.as_str() {
+        "utf8" => "utf-8".to_string(),
+        "latin1" | "l1" => "latin-1".to_string(),
+        "utf16" => "utf-16".to_string(),
+        _ => name,
+    }
+}
+
+/// Adds or replaces the codec registered under `name`, the way `codecs.register()` extends
+/// CPython's registry, so other modules can add encodings without this one knowing about them.
+pub fn register(name: &str, codec: Codec) {
+    CODECS.write().unwrap().insert(normalize(name), codec);
+}
+
+fn lookup(name: &str, vm: &VirtualMachine) -> PyResult<Codec> {
+    CODECS
+        .read()
+        .unwrap()
+        .get(&normalize(name))
+        .copied()
+        .ok_or_else(|| vm.new_lookup_error(format!("unknown encoding: {}", name)))
+}
+
+pub fn encode(name: &str, s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    (lookup(name, vm)?.encode)(s, vm)
+}
+
+pub fn decode(name: &str, bytes: &[u8], errors: ErrorHandling, vm: &VirtualMachine) -> PyResult<String> {
+    (lookup(name, vm)?.decode)(bytes, errors, vm)
+}
+
+fn encode_utf8(s: &str, _vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    Ok(s.as_bytes().to_vec())
+}
+
+fn decode_utf8(bytes: &[u8], errors: ErrorHandling, vm: &VirtualMachine) -> PyResult<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s.to_string()),
+        Err(e) => {
+            let valid_up_to = e.valid_up_to();
+            let mut out = String::from_utf8_lossy(&bytes[..valid_up_to]).into_owned();
+            let rest = &bytes[valid_up_to..];
+            let skip = e.error_len().unwrap_or(rest.len()).max(1);
+            match errors {
+                ErrorHandling::Strict => Err(vm.new_unicode_decode_error(format!(
+                    "'utf-8' codec can't decode byte 0x{:02x} in position {}: invalid start byte",
+                    rest[0], valid_up_to
+                ))),
+                ErrorHandling::Ignore => {
+                    out.push_str(&decode_utf8(&rest[skip..], errors, vm)?);
+                    Ok(out)
+                }
+                ErrorHandling::Replace => {
+                    out.push('\u{FFFD}');
+                    out.push_str(&decode_utf8(&rest[skip..], errors, vm)?);
+                    Ok(out)
+                }
+            }
+        }
+    }
+}
+
+fn encode_ascii(s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    match s.chars().position(|c| !c.is_ascii()) {
+        None => Ok(s.bytes().collect()),
+        Some(pos) => Err(vm.new_unicode_encode_error(format!(
+            "'ascii' codec can't encode character '\\u{:04x}' in position {}: ordinal not in range(128)",
+            s.chars().nth(pos).unwrap() as u32,
+            pos
+        ))),
+    }
+}
+
+fn decode_ascii(bytes: &[u8], errors: ErrorHandling, vm: &VirtualMachine) -> PyResult<String> {
+    let mut out = String::with_capacity(bytes.len());
+    for (i, &b) in bytes.iter().enumerate() {
+        if b.is_ascii() {
+            out.push(b as char);
+        } else {
+            match errors {
+                ErrorHandling::Strict => {
+                    return Err(vm.new_unicode_decode_error(format!(
+                        "'ascii' codec can't decode byte 0x{:02x} in position {}: ordinal not in range(128)",
+                        b, i
+                    )))
+                }
+                ErrorHandling::Ignore => {}
+                ErrorHandling::Replace => out.push('\u{FFFD}'),
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn encode_latin1(s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    match s.chars().position(|c| c as u32 > 0xff) {
+        None => Ok(s.chars().map(|c| c as u32 as u8).collect()),
+        Some(pos) => Err(vm.new_unicode_encode_error(format!(
+            "'latin-1' codec can't encode character '\\u{:04x}' in position {}: ordinal not in range(256)",
+            s.chars().nth(pos).unwrap() as u32,
+            pos
+        ))),
+    }
+}
+
+fn decode_latin1(bytes: &[u8], _errors: ErrorHandling, _vm: &VirtualMachine) -> PyResult<String> {
+    // Every byte value is a valid Latin-1 code point, so this never fails.
+    Ok(bytes.iter().map(|&b| b as char).collect())
+}
+
+fn encode_utf16(s: &str, _vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    let mut bytes = vec![0xff, 0xfe]; // little-endian BOM, matching CPython's "utf-16"
+    for unit in s.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+fn decode_utf16(bytes: &[u8], errors: ErrorHandling, vm: &VirtualMachine) -> PyResult<String> {
+    let (body, little_endian) = match bytes {
+        [0xff, 0xfe, rest @ ..] => (rest, true),
+        [0xfe, 0xff, rest @ ..] => (rest, false),
+        rest => (rest, true),
+    };
+    let whole_units = body.len() - body.len() % 2;
+    let units: Vec<u16> = body[..whole_units]
+        .chunks(2)
+        .map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    if whole_units < body.len() && errors == ErrorHandling::Strict {
+        return Err(vm.new_unicode_decode_error("truncated utf-16 data".to_string()));
+    }
+    match (String::from_utf16(&units), errors) {
+        (Ok(s), _) => Ok(s),
+        (Err(_), ErrorHandling::Strict) => {
+            Err(vm.new_unicode_decode_error("utf-16 decoding error: invalid surrogate pair".to_string()))
+        }
+        (Err(_), _) => Ok(String::from_utf16_lossy(&units)),
+    }
+}
+
+/// Treats `str` as a transparent carrier for raw bytes (one `char` per byte, code points
+/// 0-255), the way a byte transform like `hex`/`base64` has to when bolted onto a
+/// text-shaped `encode`/`decode` signature.
+fn encode_base64(s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let raw = latin1_bytes(s, vm)?;
+    let mut out = Vec::with_capacity((raw.len() + 2) / 3 * 4);
+    for chunk in raw.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+        let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+        out.push(ALPHABET[(n >> 18 & 0x3f) as usize]);
+        out.push(ALPHABET[(n >> 12 & 0x3f) as usize]);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6 & 0x3f) as usize] } else { b'=' });
+        out.push(if chunk.len() > 2 { ALPHABET[(n & 0x3f) as usize] } else { b'=' });
+    }
+    Ok(out)
+}
+
+fn decode_base64(bytes: &[u8], errors: ErrorHandling, vm: &VirtualMachine) -> PyResult<String> {
+    fn value(b: u8) -> Option<u32> {
+        match b {
+            b'A'..=b'Z' => Some(u32::from(b - b'A')),
+            b'a'..=b'z' => Some(u32::from(b - b'a') + 26),
+            b'0'..=b'9' => Some(u32::from(b - b'0') + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+    let filtered: Vec<u8> = bytes.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+    let mut out = Vec::new();
+    for chunk in filtered.chunks(4) {
+        if chunk.len() < 2 {
+            if errors == ErrorHandling::Strict {
+                return Err(vm.new_value_error("Invalid base64-encoded string".to_string()));
+            }
+            break;
+        }
+        let pad = chunk.iter().filter(|&&b| b == b'=').count();
+        let mut n: u32 = 0;
+        let mut ok = true;
+        for &b in chunk {
+            n <<= 6;
+            if b == b'=' {
+                continue;
+            }
+            match value(b) {
+                Some(v) => n |= v,
+                None => {
+                    ok = false;
+                    break;
+                }
+            }
+        }
+        if !ok {
+            match errors {
+                ErrorHandling::Strict => return Err(vm.new_value_error("Invalid base64-encoded string".to_string())),
+                ErrorHandling::Ignore => continue,
+                ErrorHandling::Replace => {
+                    out.push(0xfffd_u32 as u8);
+                    continue;
+                }
+            }
+        }
+        out.push((n >> 16) as u8);
+        if pad < 2 {
+            out.push((n >> 8) as u8);
+        }
+        if pad < 1 {
+            out.push(n as u8);
+        }
+    }
+    Ok(out.into_iter().map(|b| b as char).collect())
+}
+
+fn encode_hex(s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    Ok(latin1_bytes(s, vm)?
+        .iter()
+        .flat_map(|b| format!("{:02x}", b).into_bytes())
+        .collect())
+}
+
+fn decode_hex(bytes: &[u8], errors: ErrorHandling, vm: &VirtualMachine) -> PyResult<String> {
+    let digits: Vec<u8> = bytes.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+    if digits.len() % 2 != 0 && errors == ErrorHandling::Strict {
+        return Err(vm.new_value_error("Odd-length string".to_string()));
+    }
+    let mut out = String::new();
+    for pair in digits.chunks(2) {
+        if pair.len() < 2 {
+            break;
+        }
+        let hex = std::str::from_utf8(pair).unwrap_or("");
+        match u8::from_str_radix(hex, 16) {
+            Ok(b) => out.push(b as char),
+            Err(_) => match errors {
+                ErrorHandling::Strict => return Err(vm.new_value_error("non-hexadecimal number found in fromhex() arg".to_string())),
+                ErrorHandling::Ignore => {}
+                ErrorHandling::Replace => out.push('\u{FFFD}'),
+            },
+        }
+    }
+    Ok(out)
+}
+
+fn latin1_bytes(s: &str, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+    s.chars()
+        .map(|c| {
+            if c as u32 <= 0xff {
+                Ok(c as u8)
+            } else {
+                Err(vm.new_value_error(format!("character '{}' is out of range for a byte transform", c)))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+
+    #[test]
+    fn normalize_accepts_common_aliases() {
+        assert_eq!(normalize("UTF8"), "utf-8");
+        assert_eq!(normalize("utf_8"), "utf-8");
+        assert_eq!(normalize("utf-8"), "utf-8");
+        assert_eq!(normalize("Latin1"), "latin-1");
+        assert_eq!(normalize("UTF16"), "utf-16");
+    }
+}
@@ -0,0 +1,118 @@
+use crate::obj::objbuffer::{find_buffer, get_buffer, slice_buffer_ref, BufferOptions, BufferProtocol, BufferRef};
+use crate::obj::objbyteinner::{clamp_index, sequence_index, SequenceIndex};
+use crate::pyobject::{PyClassImpl, PyContext, PyObjectRef, PyRef, PyResult, PyValue};
+use crate::vm::VirtualMachine;
+
+use super::objtype::PyClassRef;
+
+/// `memoryview(obj)` borrows the buffer-exporting object's storage directly instead of
+/// copying it. `start`/`len` are the window this view covers within `obj`'s buffer, captured
+/// at construction (or slicing) time; that's safe because holding the export blocks `obj`
+/// from being resized while this view is alive.
+#[derive(Debug)]
+pub struct PyMemoryView {
+    obj: PyObjectRef,
+    start: usize,
+    len: usize,
+}
+
+type PyMemoryViewRef = PyRef<PyMemoryView>;
+
+impl PyValue for PyMemoryView {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.ctx.memoryview_type()
+    }
+}
+
+impl Drop for PyMemoryView {
+    fn drop(&mut self) {
+        if let Some(buffer) = find_buffer(&self.obj) {
+            buffer.dec_export();
+        }
+    }
+}
+
+impl BufferProtocol for PyMemoryView {
+    fn get_options(&self) -> BufferOptions {
+        let readonly = find_buffer(&self.obj)
+            .map(|buffer| buffer.get_options().readonly)
+            .unwrap_or(true);
+        BufferOptions {
+            readonly,
+            len: self.len,
+        }
+    }
+
+    fn get_bytes(&self) -> BufferRef {
+        match find_buffer(&self.obj) {
+            Some(buffer) => slice_buffer_ref(buffer.get_bytes(), self.start, self.len),
+            None => BufferRef::Slice(&[]),
+        }
+    }
+}
+
+#[pyimpl(__inside_vm)]
+impl PyMemoryViewRef {
+    #[pymethod(name = "__new__")]
+    fn memoryview_new(
+        cls: PyClassRef,
+        obj: PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyMemoryViewRef> {
+        // Validate eagerly so `memoryview(1)` fails at construction time rather than on
+        // first use, and mark the export so `obj` can't be resized while this view is alive.
+        let len = get_buffer(vm, &obj)?.get_options().len;
+        get_buffer(vm, &obj)?.inc_export();
+        // `Drop` undoes this export; if `into_ref_with_type` rejects `cls`, the view it was
+        // given is dropped there, so the export is released on that path too.
+        PyMemoryView { obj, start: 0, len }.into_ref_with_type(vm, cls)
+    }
+
+    #[pymethod(name = "__len__")]
+    fn len(self, _vm: &VirtualMachine) -> usize {
+        self.len
+    }
+
+    #[pymethod(name = "tobytes")]
+    fn tobytes(self, _vm: &VirtualMachine) -> Vec<u8> {
+        self.get_bytes().to_vec()
+    }
+
+    #[pymethod(name = "__getitem__")]
+    fn getitem(self, needle: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        match sequence_index(vm, &needle, self.len)? {
+            SequenceIndex::Int(i) => {
+                let index = if i < 0 { i + self.len as isize } else { i };
+                if index < 0 || index as usize >= self.len {
+                    return Err(vm.new_index_error("index out of range".to_string()));
+                }
+                Ok(vm.new_int(self.get_bytes()[index as usize]))
+            }
+            SequenceIndex::Slice(range, step) => {
+                if step != 1 {
+                    return Err(vm.new_not_implemented_error(
+                        "memoryview slicing with step != 1 is not implemented".to_string(),
+                    ));
+                }
+                let start = clamp_index(range.start, self.len);
+                let stop = clamp_index(range.end, self.len).max(start);
+
+                // Re-export over the same underlying object so the new view keeps the buffer
+                // pinned for as long as it (not just the original view) is alive.
+                find_buffer(&self.obj)
+                    .ok_or_else(|| vm.new_buffer_error("underlying buffer is no longer available".to_string()))?
+                    .inc_export();
+                let sliced = PyMemoryView {
+                    obj: self.obj.clone(),
+                    start: self.start + start,
+                    len: stop - start,
+                };
+                Ok(sliced.into_ref(vm).into_object())
+            }
+        }
+    }
+}
+
+pub fn init(context: &PyContext) {
+    PyMemoryViewRef::extend_class(context, &context.memoryview_type);
+}